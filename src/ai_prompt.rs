@@ -0,0 +1,149 @@
+use crate::git_entity::git_commit::GitCommit;
+
+pub struct AiPrompt {
+    pub system_prompt: String,
+    pub user_prompt: String,
+}
+
+impl AiPrompt {
+    pub fn for_explain(diff: &str, query: &Option<String>, recent_commits: &[String]) -> Self {
+        let system_prompt = "You are an expert software engineer that explains git changes \
+            clearly and concisely to another engineer."
+            .to_string();
+
+        let mut user_prompt = match query {
+            Some(query) => format!("Given the following diff, answer this question: \"{query}\"\n\n```diff\n{diff}\n```"),
+            None => format!("Explain the following diff in a few sentences:\n\n```diff\n{diff}\n```"),
+        };
+
+        Self::append_recent_commits(&mut user_prompt, recent_commits);
+
+        Self {
+            system_prompt,
+            user_prompt,
+        }
+    }
+
+    pub fn for_draft(
+        diff: &str,
+        context: &Option<String>,
+        conventional: bool,
+        recent_commits: &[String],
+    ) -> Self {
+        let mut system_prompt = "You are an expert software engineer that writes concise, \
+            informative git commit messages based on a diff. Reply with the commit message only, \
+            no surrounding commentary or markdown formatting."
+            .to_string();
+
+        if conventional {
+            system_prompt.push_str(
+                " Follow the Conventional Commits format exactly: \
+                `<type>[optional scope]: <description>`, optionally followed by a blank line \
+                and a body. Choose `type` from feat, fix, docs, style, refactor, perf, test, \
+                build, ci, chore, or revert. Keep the subject line under 50 characters and wrap \
+                the body at 72 characters. Never start the description with \"wip\".",
+            );
+        }
+
+        let mut user_prompt = match context {
+            Some(context) => format!(
+                "Write a commit message for this diff. Additional context from the author: \"{context}\"\n\n```diff\n{diff}\n```"
+            ),
+            None => format!("Write a commit message for this diff:\n\n```diff\n{diff}\n```"),
+        };
+
+        Self::append_recent_commits(&mut user_prompt, recent_commits);
+
+        Self {
+            system_prompt,
+            user_prompt,
+        }
+    }
+
+    /// Appends recent commit subjects as style context, if any were requested via `--commits`.
+    fn append_recent_commits(user_prompt: &mut String, recent_commits: &[String]) {
+        if recent_commits.is_empty() {
+            return;
+        }
+
+        user_prompt.push_str(&format!(
+            "\n\nFor style reference, here are the subjects of the {} most recent commits in this repository:\n{}",
+            recent_commits.len(),
+            recent_commits
+                .iter()
+                .map(|subject| format!("- {subject}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ));
+    }
+
+    /// Summarizes a single file's chunk of a larger diff, as the "map" step of a map-reduce pass
+    /// over a changeset that's too large to send to the provider in one request.
+    pub fn for_diff_chunk(chunk: &str) -> Self {
+        let system_prompt = "You are an expert software engineer. Summarize the following part \
+            of a larger diff concisely, in a sentence or two, keeping the affected file name and \
+            the nature of the change."
+            .to_string();
+
+        let user_prompt = format!("```diff\n{chunk}\n```");
+
+        Self {
+            system_prompt,
+            user_prompt,
+        }
+    }
+
+    /// Merges per-file diff summaries into one coherent overview, as the "reduce" step of a
+    /// map-reduce pass over a large changeset.
+    pub fn for_diff_merge(summaries: &[String]) -> Self {
+        let system_prompt = "You are an expert software engineer. You are given per-file \
+            summaries of a single changeset; merge them into one coherent, concise overview of \
+            the overall change, as if you were looking at the full diff."
+            .to_string();
+
+        let user_prompt = summaries
+            .iter()
+            .enumerate()
+            .map(|(i, summary)| format!("File {}: {summary}", i + 1))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Self {
+            system_prompt,
+            user_prompt,
+        }
+    }
+
+    /// Builds a single prompt that asks the provider for one human-readable changelog line per
+    /// commit, in order, for commits whose subject didn't parse as a Conventional Commit.
+    pub fn for_changelog_summary(commits: &[GitCommit]) -> Self {
+        let system_prompt = "You are an expert software engineer that writes one-line, \
+            user-facing changelog entries from commit messages and diffs. Reply with exactly \
+            one line per commit, in the given order, as a markdown list item, and nothing else."
+            .to_string();
+
+        let entries = commits
+            .iter()
+            .enumerate()
+            .map(|(i, commit)| {
+                format!(
+                    "Commit {}:\nSubject: {}\n```diff\n{}\n```",
+                    i + 1,
+                    commit.message.lines().next().unwrap_or_default(),
+                    commit.diff
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        let user_prompt = format!(
+            "Summarize each of the following {} commits as one changelog line:\n\n{entries}",
+            commits.len()
+        );
+
+        Self {
+            system_prompt,
+            user_prompt,
+        }
+    }
+}
@@ -0,0 +1,392 @@
+use crate::ai_prompt::AiPrompt;
+use crate::error::LumenError;
+use crate::git_entity::{git_commit::GitCommit, git_diff::GitDiff, GitEntity};
+use crate::provider::LumenProvider;
+use dialoguer::{FuzzySelect, Select};
+use std::process::Command;
+
+/// Rough token estimate used for diff budgeting: about 4 characters per token.
+const CHARS_PER_TOKEN: usize = 4;
+
+pub enum CommandType {
+    Explain {
+        git_entity: GitEntity,
+        query: Option<String>,
+    },
+    List,
+    Draft {
+        context: Option<String>,
+        action: DraftAction,
+        yes: bool,
+        conventional: bool,
+    },
+    Changelog {
+        from: Option<String>,
+        to: Option<String>,
+    },
+}
+
+/// How many times to regenerate a non-compliant conventional commit message (with the same
+/// prompt) before giving up.
+const MAX_CONVENTIONAL_RETRIES: u32 = 2;
+
+/// What to do with a drafted commit message once it's been accepted.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum DraftAction {
+    /// Run `git commit` with the message.
+    Commit,
+    /// Put the message on the clipboard.
+    Copy,
+    /// Print the message to stdout (default).
+    Print,
+}
+
+pub struct LumenCommand {
+    provider: LumenProvider,
+    max_tokens: usize,
+    commits: usize,
+}
+
+impl LumenCommand {
+    pub fn new(provider: LumenProvider, max_tokens: usize, commits: usize) -> Self {
+        Self {
+            provider,
+            max_tokens,
+            commits,
+        }
+    }
+
+    pub async fn execute(&self, command_type: CommandType) -> Result<(), LumenError> {
+        match command_type {
+            CommandType::Explain { git_entity, query } => self.explain(&git_entity, &query).await,
+            CommandType::List => self.list().await,
+            CommandType::Draft {
+                context,
+                action,
+                yes,
+                conventional,
+            } => self.draft(context, action, yes, conventional).await,
+            CommandType::Changelog { from, to } => self.changelog(from, to).await,
+        }
+    }
+
+    async fn explain(&self, git_entity: &GitEntity, query: &Option<String>) -> Result<(), LumenError> {
+        let diff = self.prepare_diff(git_entity.diff()).await?;
+        let recent_commits = self.recent_commit_subjects()?;
+        let prompt = AiPrompt::for_explain(&diff, query, &recent_commits);
+        let explanation = self.provider.complete(&prompt.system_prompt, &prompt.user_prompt).await?;
+        println!("{explanation}");
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<(), LumenError> {
+        let output = Command::new("git")
+            .args(["log", "--pretty=format:%h %s"])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(LumenError::Git(String::from_utf8_lossy(&output.stderr).into()));
+        }
+
+        let lines: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect();
+
+        if lines.is_empty() {
+            return Err(LumenError::Git("no commits found".into()));
+        }
+
+        let selection = FuzzySelect::new()
+            .with_prompt("Select a commit to explain")
+            .items(&lines)
+            .default(0)
+            .interact()
+            .map_err(|e| LumenError::Provider(format!("selection failed: {e}")))?;
+
+        let sha = lines[selection]
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        let git_entity = GitEntity::Commit(GitCommit::new(sha)?);
+        self.explain(&git_entity, &None).await
+    }
+
+    async fn draft(
+        &self,
+        context: Option<String>,
+        action: DraftAction,
+        yes: bool,
+        conventional: bool,
+    ) -> Result<(), LumenError> {
+        let diff = self.prepare_diff(&GitDiff::new(true)?.diff).await?;
+        let mut message = self.generate_compliant_draft(&diff, &context, conventional).await?;
+
+        if !yes {
+            loop {
+                println!("\n{message}\n");
+
+                let options = ["Accept", "Edit", "Regenerate", "Cancel"];
+                let selection = Select::new()
+                    .with_prompt("What would you like to do with this commit message?")
+                    .items(&options)
+                    .default(0)
+                    .interact()
+                    .map_err(|e| LumenError::Provider(format!("selection failed: {e}")))?;
+
+                match options[selection] {
+                    "Accept" => break,
+                    "Edit" => {
+                        message = dialoguer::Editor::new()
+                            .edit(&message)
+                            .map_err(|e| LumenError::Provider(format!("edit failed: {e}")))?
+                            .unwrap_or(message);
+                    }
+                    "Regenerate" => {
+                        message = self.generate_compliant_draft(&diff, &context, conventional).await?
+                    }
+                    _ => return Ok(()),
+                }
+            }
+        }
+
+        match action {
+            DraftAction::Commit => self.commit(&message),
+            DraftAction::Copy => self.copy_to_clipboard(&message),
+            DraftAction::Print => {
+                println!("{message}");
+                Ok(())
+            }
+        }
+    }
+
+    async fn generate_draft(
+        &self,
+        diff: &str,
+        context: &Option<String>,
+        conventional: bool,
+    ) -> Result<String, LumenError> {
+        let recent_commits = self.recent_commit_subjects()?;
+        let prompt = AiPrompt::for_draft(diff, context, conventional, &recent_commits);
+        let message = self.provider.complete(&prompt.system_prompt, &prompt.user_prompt).await?;
+        Ok(message.trim().to_string())
+    }
+
+    /// Generates a draft message, and, if `conventional` is set, retries with the provider a
+    /// few times until the result parses as a Conventional Commit and isn't a WIP placeholder.
+    async fn generate_compliant_draft(
+        &self,
+        diff: &str,
+        context: &Option<String>,
+        conventional: bool,
+    ) -> Result<String, LumenError> {
+        let mut message = self.generate_draft(diff, context, conventional).await?;
+
+        if !conventional {
+            return Ok(message);
+        }
+
+        for _ in 0..MAX_CONVENTIONAL_RETRIES {
+            match validate_conventional(&message) {
+                Ok(()) => return Ok(message),
+                Err(_) => message = self.generate_draft(diff, context, conventional).await?,
+            }
+        }
+
+        validate_conventional(&message)?;
+        Ok(message)
+    }
+
+    async fn changelog(&self, from: Option<String>, to: Option<String>) -> Result<(), LumenError> {
+        let from = match from {
+            Some(from) => from,
+            None => Self::latest_tag()?,
+        };
+        let to = to.unwrap_or_else(|| "HEAD".to_string());
+
+        let shas = Self::commit_shas_between(&from, &to)?;
+        if shas.is_empty() {
+            return Err(LumenError::Git(format!("no commits found between {from}..{to}")));
+        }
+
+        let mut features = Vec::new();
+        let mut fixes = Vec::new();
+        let mut refactors = Vec::new();
+        let mut other = Vec::new();
+        let mut unparsed = Vec::new();
+
+        for sha in shas {
+            let commit = GitCommit::new(sha)?;
+            let subject = commit.message.lines().next().unwrap_or_default();
+
+            match git_conventional::Commit::parse(subject) {
+                Ok(parsed) => {
+                    let entry = format!("- {} ({:.7})", parsed.description(), commit.sha);
+                    match parsed.type_().as_str() {
+                        "feat" => features.push(entry),
+                        "fix" => fixes.push(entry),
+                        "refactor" => refactors.push(entry),
+                        _ => other.push(entry),
+                    }
+                }
+                Err(_) => unparsed.push(commit),
+            }
+        }
+
+        if !unparsed.is_empty() {
+            other.extend(self.summarize_unparsed(&unparsed).await?);
+        }
+
+        let mut changelog = format!("## Changelog ({from}..{to})\n");
+        Self::push_section(&mut changelog, "Features", &features);
+        Self::push_section(&mut changelog, "Bug Fixes", &fixes);
+        Self::push_section(&mut changelog, "Refactors", &refactors);
+        Self::push_section(&mut changelog, "Other", &other);
+
+        println!("{}", changelog.trim_end());
+        Ok(())
+    }
+
+    async fn summarize_unparsed(&self, commits: &[GitCommit]) -> Result<Vec<String>, LumenError> {
+        let prompt = AiPrompt::for_changelog_summary(commits);
+        let response = self.provider.complete(&prompt.system_prompt, &prompt.user_prompt).await?;
+
+        Ok(response
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .zip(commits)
+            .map(|(line, commit)| format!("- {} ({:.7})", line.trim_start_matches(['-', ' ']), commit.sha))
+            .collect())
+    }
+
+    fn push_section(changelog: &mut String, title: &str, entries: &[String]) {
+        if entries.is_empty() {
+            return;
+        }
+
+        changelog.push_str(&format!("\n### {title}\n\n"));
+        changelog.push_str(&entries.join("\n"));
+        changelog.push('\n');
+    }
+
+    fn latest_tag() -> Result<String, LumenError> {
+        let output = Command::new("git").args(["describe", "--tags", "--abbrev=0"]).output()?;
+
+        if !output.status.success() {
+            return Err(LumenError::Git(
+                "no starting ref given and no tags found; pass `from` explicitly".into(),
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn commit_shas_between(from: &str, to: &str) -> Result<Vec<String>, LumenError> {
+        let output = Command::new("git")
+            .args(["log", "--format=%H", &format!("{from}..{to}")])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(LumenError::Git(format!(
+                "failed to list commits between {from}..{to}: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Returns `diff` as-is if it fits the token budget, otherwise map-reduce summarizes it
+    /// per-file and merges the summaries into a single stand-in diff description.
+    async fn prepare_diff(&self, diff: &str) -> Result<String, LumenError> {
+        if Self::estimate_tokens(diff) <= self.max_tokens {
+            return Ok(diff.to_string());
+        }
+
+        self.summarize_large_diff(diff).await
+    }
+
+    async fn summarize_large_diff(&self, diff: &str) -> Result<String, LumenError> {
+        let mut summaries = Vec::new();
+
+        for chunk in GitDiff::split_by_file(diff) {
+            let prompt = AiPrompt::for_diff_chunk(&chunk);
+            summaries.push(self.provider.complete(&prompt.system_prompt, &prompt.user_prompt).await?);
+        }
+
+        let prompt = AiPrompt::for_diff_merge(&summaries);
+        self.provider.complete(&prompt.system_prompt, &prompt.user_prompt).await
+    }
+
+    fn estimate_tokens(text: &str) -> usize {
+        text.len() / CHARS_PER_TOKEN
+    }
+
+    fn recent_commit_subjects(&self) -> Result<Vec<String>, LumenError> {
+        if self.commits == 0 {
+            return Ok(Vec::new());
+        }
+
+        let output = Command::new("git")
+            .args(["log", &format!("-{}", self.commits), "--format=%s"])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(LumenError::Git(String::from_utf8_lossy(&output.stderr).into()));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn commit(&self, message: &str) -> Result<(), LumenError> {
+        let output = Command::new("git").args(["commit", "-m", message]).output()?;
+
+        if !output.status.success() {
+            return Err(LumenError::Git(String::from_utf8_lossy(&output.stderr).into()));
+        }
+
+        print!("{}", String::from_utf8_lossy(&output.stdout));
+        Ok(())
+    }
+
+    fn copy_to_clipboard(&self, message: &str) -> Result<(), LumenError> {
+        let mut clipboard = arboard::Clipboard::new()
+            .map_err(|e| LumenError::Provider(format!("failed to access clipboard: {e}")))?;
+
+        clipboard
+            .set_text(message)
+            .map_err(|e| LumenError::Provider(format!("failed to copy to clipboard: {e}")))?;
+
+        println!("Commit message copied to clipboard.");
+        Ok(())
+    }
+}
+
+/// Checks that `message` is a well-formed Conventional Commit and isn't a WIP placeholder.
+fn validate_conventional(message: &str) -> Result<(), LumenError> {
+    let subject = message.lines().next().unwrap_or_default();
+
+    if subject
+        .trim_start()
+        .to_lowercase()
+        .starts_with("wip")
+    {
+        return Err(LumenError::InvalidCommitMessage {
+            reason: format!("subject looks like a work-in-progress placeholder: \"{subject}\""),
+        });
+    }
+
+    git_conventional::Commit::parse(message).map_err(|e| LumenError::InvalidCommitMessage {
+        reason: format!("not a valid Conventional Commit: {e}"),
+    })?;
+
+    Ok(())
+}
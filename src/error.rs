@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LumenError {
+    #[error("Invalid arguments: {0}")]
+    InvalidArguments(String),
+
+    #[error("Git error: {0}")]
+    Git(String),
+
+    #[error("Provider error: {0}")]
+    Provider(String),
+
+    #[error("Invalid commit message: {reason}")]
+    InvalidCommitMessage { reason: String },
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
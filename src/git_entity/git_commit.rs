@@ -0,0 +1,69 @@
+use crate::error::LumenError;
+use std::process::Command;
+
+pub struct GitCommit {
+    pub sha: String,
+    pub message: String,
+    pub diff: String,
+}
+
+impl GitCommit {
+    pub fn new(sha: String) -> Result<Self, LumenError> {
+        let sha = Self::resolve_sha(&sha)?;
+
+        let message_output = Command::new("git")
+            .args(["show", "--no-patch", "--pretty=format:%H%n%B", &sha])
+            .output()?;
+
+        if !message_output.status.success() {
+            return Err(LumenError::Git(format!(
+                "failed to find commit '{sha}': {}",
+                String::from_utf8_lossy(&message_output.stderr)
+            )));
+        }
+
+        let message_output = String::from_utf8_lossy(&message_output.stdout);
+        let mut lines = message_output.lines();
+        let full_sha = lines.next().unwrap_or(&sha).to_string();
+        let message = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+
+        let diff_output = Command::new("git")
+            .args(["show", "--pretty=format:", &sha])
+            .output()?;
+
+        if !diff_output.status.success() {
+            return Err(LumenError::Git(format!(
+                "failed to diff commit '{sha}': {}",
+                String::from_utf8_lossy(&diff_output.stderr)
+            )));
+        }
+
+        Ok(Self {
+            sha: full_sha,
+            message,
+            diff: String::from_utf8_lossy(&diff_output.stdout).trim().to_string(),
+        })
+    }
+
+    /// Expands a possibly-abbreviated commit identifier (e.g. a 7-character SHA prefix) to its
+    /// unique full SHA, returning a clear error if the prefix is ambiguous or matches nothing.
+    fn resolve_sha(sha: &str) -> Result<String, LumenError> {
+        let is_full_sha = sha.len() == 40 && sha.chars().all(|c| c.is_ascii_hexdigit());
+        if is_full_sha {
+            return Ok(sha.to_string());
+        }
+
+        let output = Command::new("git")
+            .args(["rev-parse", "--verify", &format!("{sha}^{{commit}}")])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(LumenError::Git(format!(
+                "'{sha}' does not uniquely match any commit: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
@@ -0,0 +1,57 @@
+use crate::error::LumenError;
+use std::process::Command;
+
+pub struct GitDiff {
+    pub diff: String,
+}
+
+impl GitDiff {
+    pub fn new(staged: bool) -> Result<Self, LumenError> {
+        let mut args = vec!["diff"];
+        if staged {
+            args.push("--staged");
+        }
+
+        let output = Command::new("git").args(args).output()?;
+
+        if !output.status.success() {
+            return Err(LumenError::Git(format!(
+                "failed to get diff: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let diff = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+        if diff.is_empty() {
+            return Err(LumenError::Git(if staged {
+                "no staged changes found".into()
+            } else {
+                "no changes found".into()
+            }));
+        }
+
+        Ok(Self { diff })
+    }
+
+    /// Splits a diff into per-file chunks, each starting at its `diff --git` header, for
+    /// map-reduce summarization of changesets too large to send in one request.
+    pub fn split_by_file(diff: &str) -> Vec<String> {
+        let mut chunks = Vec::new();
+        let mut current = String::new();
+
+        for line in diff.lines() {
+            if line.starts_with("diff --git") && !current.is_empty() {
+                chunks.push(std::mem::take(&mut current));
+            }
+            current.push_str(line);
+            current.push('\n');
+        }
+
+        if !current.is_empty() {
+            chunks.push(current);
+        }
+
+        chunks
+    }
+}
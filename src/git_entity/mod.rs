@@ -0,0 +1,19 @@
+pub mod git_commit;
+pub mod git_diff;
+
+use git_commit::GitCommit;
+use git_diff::GitDiff;
+
+pub enum GitEntity {
+    Commit(GitCommit),
+    Diff(GitDiff),
+}
+
+impl GitEntity {
+    pub fn diff(&self) -> &str {
+        match self {
+            GitEntity::Commit(commit) => &commit.diff,
+            GitEntity::Diff(diff) => &diff.diff,
+        }
+    }
+}
@@ -1,6 +1,8 @@
-use clap::{command, Parser, Subcommand, ValueEnum};
+use clap::{command, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
 use error::LumenError;
 use git_entity::{git_commit::GitCommit, git_diff::GitDiff, GitEntity};
+use std::io;
 use std::process;
 
 mod ai_prompt;
@@ -25,9 +27,23 @@ struct Cli {
     #[arg(short = 'k', long = "api-key", env = "LUMEN_API_KEY")]
     api_key: Option<String>,
 
+    /// Shell command to run to obtain the API key at runtime (e.g. `pass show openai`), takes
+    /// precedence over --api-key / LUMEN_API_KEY
+    #[arg(long = "api-key-cmd", env = "LUMEN_API_KEY_CMD")]
+    api_key_cmd: Option<String>,
+
     #[arg(short = 'm', long = "model", env = "LUMEN_AI_MODEL")]
     model: Option<String>,
 
+    /// Maximum diff size, in estimated tokens, to send to the provider in one request before
+    /// falling back to per-file map-reduce summarization
+    #[arg(long = "max-tokens", env = "LUMEN_MAX_TOKENS", default_value_t = 4096)]
+    max_tokens: usize,
+
+    /// Include the subjects of the last N commits as style context for the provider
+    #[arg(long = "commits", default_value_t = 0)]
+    commits: usize,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -68,6 +84,39 @@ enum Commands {
         /// Add context to communicate intent
         #[arg(short, long)]
         context: Option<String>,
+
+        /// Commit the staged changes with the generated message
+        #[arg(long, group = "action")]
+        commit: bool,
+
+        /// Copy the generated message to the clipboard
+        #[arg(long, group = "action")]
+        copy: bool,
+
+        /// Print the generated message only (default)
+        #[arg(long, group = "action")]
+        no_commit: bool,
+
+        /// Skip the confirm/edit/regenerate prompt and act immediately
+        #[arg(short = 'y', long)]
+        yes: bool,
+
+        /// Constrain the generated message to the Conventional Commits format
+        #[arg(long)]
+        conventional: bool,
+    },
+    /// Generate tab-completion scripts for your shell
+    Completions {
+        /// The shell to generate the completion script for
+        shell: Shell,
+    },
+    /// Summarize a commit range into a grouped, markdown changelog
+    Changelog {
+        /// The starting ref, exclusive (defaults to the latest tag)
+        from: Option<String>,
+
+        /// The ending ref, inclusive (defaults to HEAD)
+        to: Option<String>,
     },
 }
 
@@ -81,9 +130,16 @@ async fn main() {
 
 async fn run() -> Result<(), LumenError> {
     let cli = Cli::parse();
+
+    if let Commands::Completions { shell } = cli.command {
+        generate_completions(shell);
+        return Ok(());
+    }
+
     let client = reqwest::Client::new();
-    let provider = provider::LumenProvider::new(client, cli.provider, cli.api_key, cli.model)?;
-    let command = command::LumenCommand::new(provider);
+    let provider =
+        provider::LumenProvider::new(client, cli.provider, cli.api_key, cli.api_key_cmd, cli.model)?;
+    let command = command::LumenCommand::new(provider, cli.max_tokens, cli.commits);
 
     match cli.command {
         Commands::Explain {
@@ -107,12 +163,42 @@ async fn run() -> Result<(), LumenError> {
                 .await?;
         }
         Commands::List => command.execute(command::CommandType::List).await?,
-        Commands::Draft { context } => {
+        Commands::Draft {
+            context,
+            commit,
+            copy,
+            no_commit: _,
+            yes,
+            conventional,
+        } => {
+            let action = if commit {
+                command::DraftAction::Commit
+            } else if copy {
+                command::DraftAction::Copy
+            } else {
+                command::DraftAction::Print
+            };
+
             command
-                .execute(command::CommandType::Draft(context))
+                .execute(command::CommandType::Draft {
+                    context,
+                    action,
+                    yes,
+                    conventional,
+                })
                 .await?
         }
+        Commands::Changelog { from, to } => {
+            command.execute(command::CommandType::Changelog { from, to }).await?
+        }
+        Commands::Completions { .. } => unreachable!("handled before provider setup"),
     }
 
     Ok(())
 }
+
+fn generate_completions(shell: Shell) {
+    let mut cmd = Cli::command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, &mut io::stdout());
+}
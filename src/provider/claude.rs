@@ -0,0 +1,41 @@
+use crate::error::LumenError;
+use serde_json::json;
+
+const DEFAULT_MODEL: &str = "claude-3-5-sonnet-20240620";
+const API_URL: &str = "https://api.anthropic.com/v1/messages";
+
+pub async fn complete(
+    client: &reqwest::Client,
+    api_key: &str,
+    model: &Option<String>,
+    system_prompt: &str,
+    user_prompt: &str,
+) -> Result<String, LumenError> {
+    let body = json!({
+        "model": model.as_deref().unwrap_or(DEFAULT_MODEL),
+        "max_tokens": 1024,
+        "system": system_prompt,
+        "messages": [
+            { "role": "user", "content": user_prompt },
+        ],
+    });
+
+    let response = client
+        .post(API_URL)
+        .header("x-api-key", api_key)
+        .header("anthropic-version", "2023-06-01")
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| LumenError::Provider(format!("claude request failed: {e}")))?;
+
+    let response: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| LumenError::Provider(format!("claude response parse failed: {e}")))?;
+
+    response["content"][0]["text"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| LumenError::Provider(format!("unexpected claude response: {response}")))
+}
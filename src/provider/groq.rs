@@ -0,0 +1,39 @@
+use crate::error::LumenError;
+use serde_json::json;
+
+const DEFAULT_MODEL: &str = "llama-3.1-70b-versatile";
+const API_URL: &str = "https://api.groq.com/openai/v1/chat/completions";
+
+pub async fn complete(
+    client: &reqwest::Client,
+    api_key: &str,
+    model: &Option<String>,
+    system_prompt: &str,
+    user_prompt: &str,
+) -> Result<String, LumenError> {
+    let body = json!({
+        "model": model.as_deref().unwrap_or(DEFAULT_MODEL),
+        "messages": [
+            { "role": "system", "content": system_prompt },
+            { "role": "user", "content": user_prompt },
+        ],
+    });
+
+    let response = client
+        .post(API_URL)
+        .bearer_auth(api_key)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| LumenError::Provider(format!("groq request failed: {e}")))?;
+
+    let response: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| LumenError::Provider(format!("groq response parse failed: {e}")))?;
+
+    response["choices"][0]["message"]["content"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| LumenError::Provider(format!("unexpected groq response: {response}")))
+}
@@ -0,0 +1,92 @@
+mod claude;
+mod groq;
+mod ollama;
+mod openai;
+mod phind;
+
+use crate::error::LumenError;
+use crate::ProviderType;
+use std::process::Command;
+
+pub struct LumenProvider {
+    client: reqwest::Client,
+    provider_type: ProviderType,
+    api_key: Option<String>,
+    model: Option<String>,
+}
+
+impl LumenProvider {
+    pub fn new(
+        client: reqwest::Client,
+        provider_type: ProviderType,
+        api_key: Option<String>,
+        api_key_cmd: Option<String>,
+        model: Option<String>,
+    ) -> Result<Self, LumenError> {
+        let api_key = match api_key_cmd {
+            Some(cmd) => Some(Self::run_api_key_cmd(&cmd)?),
+            None => api_key,
+        };
+
+        if matches!(
+            provider_type,
+            ProviderType::Openai | ProviderType::Groq | ProviderType::Claude
+        ) && api_key.is_none()
+        {
+            return Err(LumenError::InvalidArguments(format!(
+                "{provider_type:?} requires an API key, pass --api-key, --api-key-cmd, or set LUMEN_API_KEY"
+            )));
+        }
+
+        Ok(Self {
+            client,
+            provider_type,
+            api_key,
+            model,
+        })
+    }
+
+    /// Runs `cmd` through the shell and returns its trimmed stdout as the API key, for
+    /// integrating with password managers and secret stores (e.g. `pass show openai`).
+    fn run_api_key_cmd(cmd: &str) -> Result<String, LumenError> {
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(cmd)
+            .output()
+            .map_err(|e| LumenError::Provider(format!("failed to run --api-key-cmd '{cmd}': {e}")))?;
+
+        if !output.status.success() {
+            return Err(LumenError::Provider(format!(
+                "--api-key-cmd '{cmd}' exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .trim_end_matches(['\n', '\r'])
+            .to_string())
+    }
+
+    pub async fn complete(&self, system_prompt: &str, user_prompt: &str) -> Result<String, LumenError> {
+        match self.provider_type {
+            ProviderType::Openai => {
+                openai::complete(&self.client, self.api_key()?, &self.model, system_prompt, user_prompt).await
+            }
+            ProviderType::Groq => {
+                groq::complete(&self.client, self.api_key()?, &self.model, system_prompt, user_prompt).await
+            }
+            ProviderType::Claude => {
+                claude::complete(&self.client, self.api_key()?, &self.model, system_prompt, user_prompt).await
+            }
+            ProviderType::Phind => phind::complete(&self.client, &self.model, system_prompt, user_prompt).await,
+            ProviderType::Ollama => ollama::complete(&self.client, &self.model, system_prompt, user_prompt).await,
+        }
+    }
+
+    fn api_key(&self) -> Result<&str, LumenError> {
+        self.api_key
+            .as_deref()
+            .ok_or_else(|| LumenError::Provider(format!("{:?} requires an API key", self.provider_type)))
+    }
+}
@@ -0,0 +1,38 @@
+use crate::error::LumenError;
+use serde_json::json;
+
+const DEFAULT_MODEL: &str = "llama3";
+const API_URL: &str = "http://localhost:11434/api/chat";
+
+pub async fn complete(
+    client: &reqwest::Client,
+    model: &Option<String>,
+    system_prompt: &str,
+    user_prompt: &str,
+) -> Result<String, LumenError> {
+    let body = json!({
+        "model": model.as_deref().unwrap_or(DEFAULT_MODEL),
+        "stream": false,
+        "messages": [
+            { "role": "system", "content": system_prompt },
+            { "role": "user", "content": user_prompt },
+        ],
+    });
+
+    let response = client
+        .post(API_URL)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| LumenError::Provider(format!("ollama request failed: {e}, is the ollama server running?")))?;
+
+    let response: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| LumenError::Provider(format!("ollama response parse failed: {e}")))?;
+
+    response["message"]["content"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| LumenError::Provider(format!("unexpected ollama response: {response}")))
+}
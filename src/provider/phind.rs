@@ -0,0 +1,50 @@
+use crate::error::LumenError;
+use serde_json::json;
+
+const DEFAULT_MODEL: &str = "Phind-34B";
+const API_URL: &str = "https://https.extension.phind.com/agent/";
+
+pub async fn complete(
+    client: &reqwest::Client,
+    model: &Option<String>,
+    system_prompt: &str,
+    user_prompt: &str,
+) -> Result<String, LumenError> {
+    let body = json!({
+        "additional_extension_context": "",
+        "allow_magic_buttons": true,
+        "is_vscode_extension": true,
+        "message_history": [
+            { "content": system_prompt, "role": "system" },
+            { "content": user_prompt, "role": "user" },
+        ],
+        "requested_model": model.as_deref().unwrap_or(DEFAULT_MODEL),
+        "user_input": user_prompt,
+    });
+
+    let response = client
+        .post(API_URL)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| LumenError::Provider(format!("phind request failed: {e}")))?;
+
+    let text = response
+        .text()
+        .await
+        .map_err(|e| LumenError::Provider(format!("phind response read failed: {e}")))?;
+
+    // Phind streams newline-delimited "data: " chunks; concatenate the content fields.
+    let message = text
+        .lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .filter_map(|chunk| serde_json::from_str::<serde_json::Value>(chunk).ok())
+        .filter_map(|value| value["choices"][0]["delta"]["content"].as_str().map(str::to_string))
+        .collect::<String>();
+
+    if message.is_empty() {
+        return Err(LumenError::Provider(format!("unexpected phind response: {text}")));
+    }
+
+    Ok(message)
+}